@@ -0,0 +1,106 @@
+//! The expected-diagnostic annotation format: a snippet's header can record
+//! what the compiler is *supposed* to do with it, so a checker can diff
+//! that intent against what actually happens.
+
+use std::fmt;
+
+use crate::header::Header;
+
+/// The compiler phase an annotation says a snippet should fail in. Ordered
+/// earliest-to-latest: a structural/signature check (`Resolve`,
+/// `TypeckCollect`) is meant to run, and reject, before a body check
+/// (`TypeckBodies`, `TraitSolve`) ever sees the item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompilerPhase {
+    Parse,
+    Resolve,
+    TypeckCollect,
+    TypeckBodies,
+    TraitSolve,
+    Codegen,
+}
+
+impl CompilerPhase {
+    fn parse(s: &str) -> Option<CompilerPhase> {
+        Some(match s.trim() {
+            "parse" => CompilerPhase::Parse,
+            "resolve" => CompilerPhase::Resolve,
+            "typeck-collect" => CompilerPhase::TypeckCollect,
+            "typeck-bodies" => CompilerPhase::TypeckBodies,
+            "trait-solve" => CompilerPhase::TraitSolve,
+            "codegen" => CompilerPhase::Codegen,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for CompilerPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CompilerPhase::Parse => "parse",
+            CompilerPhase::Resolve => "resolve",
+            CompilerPhase::TypeckCollect => "typeck-collect",
+            CompilerPhase::TypeckBodies => "typeck-bodies",
+            CompilerPhase::TraitSolve => "trait-solve",
+            CompilerPhase::Codegen => "codegen",
+        };
+        f.write_str(s)
+    }
+}
+
+/// What the annotation says currently happens, as opposed to what should
+/// ideally happen (a clean rejection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrentBehavior {
+    Ice,
+    CorrectRejection,
+    /// rustc accepts the snippet with no diagnostics at all — a silent
+    /// miscompile/soundness hole, not a rejection of any kind.
+    SilentAccept,
+}
+
+impl CurrentBehavior {
+    fn parse(s: &str) -> Option<CurrentBehavior> {
+        Some(match s.trim().to_ascii_lowercase().as_str() {
+            "ice" => CurrentBehavior::Ice,
+            "correct rejection" => CurrentBehavior::CorrectRejection,
+            "silent accept" => CurrentBehavior::SilentAccept,
+            _ => return None,
+        })
+    }
+}
+
+/// The intended compiler behavior for a snippet, parsed from its header:
+///
+/// ```text
+/// // expected error: E0038
+/// // expected phase: resolve
+/// // current behavior: ICE
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotation {
+    pub expected_error: Option<String>,
+    pub expected_phase: Option<CompilerPhase>,
+    pub current_behavior: Option<CurrentBehavior>,
+}
+
+impl Annotation {
+    pub fn parse(header: &Header) -> Annotation {
+        Annotation {
+            expected_error: header.get("expected error").map(str::to_string),
+            expected_phase: header.get("expected phase").and_then(CompilerPhase::parse),
+            current_behavior: header
+                .get("current behavior")
+                .and_then(CurrentBehavior::parse),
+        }
+    }
+
+    /// Whether the header carried any annotation fields at all; snippets
+    /// without one are skipped by the checker rather than treated as a
+    /// mismatch.
+    pub fn is_present(&self) -> bool {
+        self.expected_error.is_some()
+            || self.expected_phase.is_some()
+            || self.current_behavior.is_some()
+    }
+}
@@ -0,0 +1,107 @@
+//! Binary search for the exact toolchain boundary where an ICE first appears
+//! (an ascending transition) or disappears once a fix has landed (a
+//! descending transition).
+
+use crate::outcome::Outcome;
+use crate::release_table::available_releases_between;
+use crate::runner::compile_with;
+use crate::version_range::Version;
+use std::fmt;
+use std::path::Path;
+
+/// The outcome of bisecting a snippet's affected range against the installed
+/// toolchains in `releases_between(lo, hi)`.
+pub enum BisectResult {
+    /// The ICE is absent at `before` (the newest candidate that doesn't
+    /// ICE) and starts at `first_ice` (the oldest candidate that does).
+    Appeared { before: Version, first_ice: Version },
+    /// The ICE is present up through `last_ice` (the newest candidate that
+    /// still ICEs) and is gone by `after` (the oldest candidate that
+    /// doesn't) — a fix landed somewhere in between.
+    Fixed { last_ice: Version, after: Version },
+    /// Every candidate in range ICEs; no boundary to report within it.
+    AlwaysIce,
+    /// No candidate in range ICEs.
+    NeverIce,
+}
+
+impl fmt::Display for BisectResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BisectResult::Appeared { before, first_ice } => {
+                write!(f, "clean through {before}, first ICEs at {first_ice}")
+            }
+            BisectResult::Fixed { last_ice, after } => {
+                write!(f, "ICEs through {last_ice}, fixed by {after}")
+            }
+            BisectResult::AlwaysIce => write!(f, "ICEs across the entire searched range"),
+            BisectResult::NeverIce => write!(f, "no ICE reproduces in the searched range"),
+        }
+    }
+}
+
+/// Binary-searches the stable releases between `lo` and `hi` for the
+/// boundary where `snippet` starts or stops ICEing, invoking the compiler
+/// once per candidate rather than walking the whole range linearly.
+///
+/// Candidates are restricted to toolchains `rustup toolchain list` reports
+/// as installed (see [`available_releases_between`]), so a release missing
+/// from the local machine is skipped rather than handed to `compile_with`
+/// where it could only ever fail with "toolchain not installed".
+///
+/// Both monotonic directions are handled: a snippet can ICE starting at
+/// some version and still ICE at `hi` (an open-ended `affected versions`
+/// range), or it can ICE at `lo` and have been fixed by `hi` (a closed
+/// range whose upper bound is meaningful, not just "last checked").
+pub fn bisect(snippet: &Path, lo: Version, hi: Version) -> std::io::Result<BisectResult> {
+    let candidates = available_releases_between(lo, hi)?;
+    if candidates.is_empty() {
+        return Ok(BisectResult::NeverIce);
+    }
+
+    let is_ice = |v: Version| -> std::io::Result<bool> {
+        Ok(compile_with(snippet, v)?.outcome == Outcome::Ice)
+    };
+
+    let lowest_ices = is_ice(candidates[0])?;
+    let highest_ices = is_ice(*candidates.last().unwrap())?;
+
+    match (lowest_ices, highest_ices) {
+        (false, false) => Ok(BisectResult::NeverIce),
+        (true, true) => Ok(BisectResult::AlwaysIce),
+        (false, true) => {
+            // Invariant: candidates[lo_idx] doesn't ICE, candidates[hi_idx] does.
+            let mut lo_idx = 0usize;
+            let mut hi_idx = candidates.len() - 1;
+            while hi_idx - lo_idx > 1 {
+                let mid = lo_idx + (hi_idx - lo_idx) / 2;
+                if is_ice(candidates[mid])? {
+                    hi_idx = mid;
+                } else {
+                    lo_idx = mid;
+                }
+            }
+            Ok(BisectResult::Appeared {
+                before: candidates[lo_idx],
+                first_ice: candidates[hi_idx],
+            })
+        }
+        (true, false) => {
+            // Invariant: candidates[lo_idx] ICEs, candidates[hi_idx] doesn't.
+            let mut lo_idx = 0usize;
+            let mut hi_idx = candidates.len() - 1;
+            while hi_idx - lo_idx > 1 {
+                let mid = lo_idx + (hi_idx - lo_idx) / 2;
+                if is_ice(candidates[mid])? {
+                    lo_idx = mid;
+                } else {
+                    hi_idx = mid;
+                }
+            }
+            Ok(BisectResult::Fixed {
+                last_ice: candidates[lo_idx],
+                after: candidates[hi_idx],
+            })
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Diffs a snippet's [`Annotation`] against what actually happens when it's
+//! compiled: which error codes rustc emits, and which phase it reached
+//! before rejecting (or crashing on) the snippet.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::annotation::{Annotation, CompilerPhase, CurrentBehavior};
+use crate::outcome::Outcome;
+use crate::signature::query_stack_frames;
+
+/// What actually happened, as observed from one compile.
+pub struct Observed {
+    pub outcome: Outcome,
+    pub error_codes: Vec<String>,
+    pub phase: Option<CompilerPhase>,
+}
+
+/// Where an annotation's expectation disagrees with what was observed.
+pub struct Mismatch {
+    pub field: &'static str,
+    pub expected: String,
+    pub observed: String,
+}
+
+/// Compiles `snippet` with `--error-format=json` and extracts the emitted
+/// error codes plus the furthest query-stack phase reached.
+pub fn observe(snippet: &Path) -> std::io::Result<Observed> {
+    let output = Command::new("rustc")
+        .arg(snippet)
+        .arg("--error-format=json")
+        .arg("-o")
+        .arg(std::env::temp_dir().join("rice-checker-out"))
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let outcome = Outcome::classify(output.status.success(), &stderr);
+    let error_codes = extract_error_codes(&stderr);
+    let phase = infer_phase(&stderr);
+
+    Ok(Observed {
+        outcome,
+        error_codes,
+        phase,
+    })
+}
+
+/// Pulls `"code":{"code":"E####"}` fields out of rustc's `--error-format=json`
+/// diagnostic stream, one JSON object per line.
+fn extract_error_codes(stderr: &str) -> Vec<String> {
+    let mut codes = Vec::new();
+    for line in stderr.lines() {
+        let Some(idx) = line.find("\"code\":\"") else {
+            continue;
+        };
+        let rest = &line[idx + "\"code\":\"".len()..];
+        if let Some(end) = rest.find('"') {
+            codes.push(rest[..end].to_string());
+        }
+    }
+    codes
+}
+
+/// Maps the innermost query-stack frame to the compiler phase it belongs to.
+/// With no query stack in `stderr` no phase is inferred; the panic location
+/// is not consulted. Queries not in this table don't contribute a guess,
+/// since guessing wrong is worse than reporting "unknown".
+fn infer_phase(stderr: &str) -> Option<CompilerPhase> {
+    let frames = query_stack_frames(stderr);
+    let innermost = frames.first()?;
+    phase_of_query(innermost)
+}
+
+fn phase_of_query(query: &str) -> Option<CompilerPhase> {
+    const TABLE: &[(&str, CompilerPhase)] = &[
+        ("resolve", CompilerPhase::Resolve),
+        ("type_of", CompilerPhase::TypeckCollect),
+        ("predicates_of", CompilerPhase::TypeckCollect),
+        ("check_well_formed", CompilerPhase::TypeckCollect),
+        ("typeck", CompilerPhase::TypeckBodies),
+        ("mir_built", CompilerPhase::TypeckBodies),
+        ("codegen_select_candidate", CompilerPhase::TraitSolve),
+        ("evaluate_obligation", CompilerPhase::TraitSolve),
+        ("fulfill_obligation", CompilerPhase::TraitSolve),
+        ("codegen", CompilerPhase::Codegen),
+        ("collect_and_partition_mono_items", CompilerPhase::Codegen),
+    ];
+    TABLE
+        .iter()
+        .find(|(needle, _)| query.contains(needle))
+        .map(|(_, phase)| *phase)
+}
+
+/// Diffs `annotation` against `observed`, returning every field that
+/// disagrees.
+pub fn diff(annotation: &Annotation, observed: &Observed) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+
+    if let Some(expected) = &annotation.expected_error {
+        if !observed.error_codes.iter().any(|c| c == expected) {
+            mismatches.push(Mismatch {
+                field: "expected error",
+                expected: expected.clone(),
+                observed: observed.error_codes.join(", "),
+            });
+        }
+    }
+
+    if let Some(expected_phase) = annotation.expected_phase {
+        if observed.phase != Some(expected_phase) {
+            mismatches.push(Mismatch {
+                field: "expected phase",
+                expected: expected_phase.to_string(),
+                observed: observed
+                    .phase
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            });
+        }
+    }
+
+    if let Some(expected_behavior) = annotation.current_behavior {
+        let actual_behavior = match observed.outcome {
+            Outcome::Ice => CurrentBehavior::Ice,
+            Outcome::CompileError => CurrentBehavior::CorrectRejection,
+            Outcome::Clean => CurrentBehavior::SilentAccept,
+        };
+        if actual_behavior != expected_behavior {
+            mismatches.push(Mismatch {
+                field: "current behavior",
+                expected: format!("{expected_behavior:?}"),
+                observed: format!("{actual_behavior:?}"),
+            });
+        }
+    }
+
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_error_codes_from_json_diagnostics() {
+        let stderr = r#"{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error"}
+{"message":"aborting due to previous error","code":null,"level":"error"}
+"#;
+        assert_eq!(extract_error_codes(stderr), vec!["E0308".to_string()]);
+    }
+
+    #[test]
+    fn infer_phase_maps_innermost_frame_via_table() {
+        let stderr = "query stack during panic:\n#0 [check_well_formed] checking that `Foo` is well-formed\n#1 [typeck] type-checking `main`\n";
+        assert_eq!(infer_phase(stderr), Some(CompilerPhase::TypeckCollect));
+    }
+
+    #[test]
+    fn infer_phase_is_none_for_unrecognized_queries() {
+        let stderr = "query stack during panic:\n#0 [some_unmapped_query] doing something\n";
+        assert_eq!(infer_phase(stderr), None);
+    }
+
+    fn observed(outcome: Outcome) -> Observed {
+        Observed {
+            outcome,
+            error_codes: vec![],
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn diff_flags_silent_accept_distinctly_from_correct_rejection() {
+        let annotation = Annotation {
+            expected_error: None,
+            expected_phase: None,
+            current_behavior: Some(CurrentBehavior::CorrectRejection),
+        };
+
+        let rejected = diff(&annotation, &observed(Outcome::CompileError));
+        assert!(rejected.is_empty());
+
+        let silently_accepted = diff(&annotation, &observed(Outcome::Clean));
+        assert_eq!(silently_accepted.len(), 1);
+        assert_eq!(silently_accepted[0].field, "current behavior");
+        assert_eq!(silently_accepted[0].observed, "SilentAccept");
+    }
+
+    #[test]
+    fn diff_is_empty_when_everything_matches() {
+        let annotation = Annotation {
+            expected_error: Some("E0038".to_string()),
+            expected_phase: Some(CompilerPhase::TypeckCollect),
+            current_behavior: Some(CurrentBehavior::Ice),
+        };
+        let observed = Observed {
+            outcome: Outcome::Ice,
+            error_codes: vec!["E0038".to_string()],
+            phase: Some(CompilerPhase::TypeckCollect),
+        };
+        assert!(diff(&annotation, &observed).is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+//! Groups the corpus into buckets that share a panic/query-stack
+//! [`Signature`], so a maintainer can see e.g. "these 7 files all hit the
+//! same `codegen_select_candidate` panic."
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::manifest::Entry;
+use crate::outcome::Outcome;
+use crate::runner::compile_with;
+use crate::signature::{signature_of, Signature};
+
+/// One signature class and the snippets observed to produce it.
+pub struct Bucket {
+    pub signature: Signature,
+    pub members: Vec<PathBuf>,
+}
+
+/// Compiles every entry at its recorded lower-bound version (the version
+/// most likely to still reproduce the crash) and groups the resulting
+/// signatures.
+///
+/// Entries that don't actually ICE at that version (a clean accept, or an
+/// ordinary diagnostic rejection) are skipped rather than bucketed: they
+/// have no crash/query-stack trace to derive a meaningful signature from, so
+/// grouping them by [`signature_of`] would merge unrelated clean rejections
+/// into the same bucket instead of leaving them out of the ICE grouping
+/// entirely.
+pub fn dedup(entries: &[Entry]) -> std::io::Result<Vec<Bucket>> {
+    let mut buckets: BTreeMap<Signature, Vec<PathBuf>> = BTreeMap::new();
+    for entry in entries {
+        let Some(range) = entry.affected else {
+            continue;
+        };
+        let result = compile_with(&entry.path, range.lower())?;
+        if result.outcome != Outcome::Ice {
+            continue;
+        }
+        let signature = signature_of(&result.stderr);
+        buckets.entry(signature).or_default().push(entry.path.clone());
+    }
+
+    Ok(buckets
+        .into_iter()
+        .map(|(signature, members)| Bucket { signature, members })
+        .collect())
+}
@@ -0,0 +1,207 @@
+//! Indexes which unstable feature gates each snippet exercises, so a
+//! maintainer can ask "which ICEs does `sized_hierarchy` need to keep
+//! green" or spot gated features the corpus doesn't cover at all.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::header::Header;
+use crate::manifest::Entry;
+
+/// Unstable features the corpus is known to target. Kept as an explicit
+/// list (rather than inferred solely from what's present) so zero-coverage
+/// gaps are visible: a feature landing here with no snippets is a known
+/// target nobody has written a reproduction for yet.
+pub const TRACKED_FEATURES: &[&str] = &[
+    "repr_simd",
+    "min_generic_const_args",
+    "sized_hierarchy",
+    "non_lifetime_binders",
+    "transmutability",
+];
+
+/// feature name -> snippets whose `#![feature(...)]` attributes enable it.
+pub struct FeatureIndex(BTreeMap<String, Vec<PathBuf>>);
+
+/// An entry whose header-declared feature list (if any) doesn't match the
+/// features its `#![feature(...)]` attributes actually enable.
+pub struct Mismatch {
+    pub path: PathBuf,
+    pub declared: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+impl FeatureIndex {
+    pub fn snippets_for(&self, feature: &str) -> &[PathBuf] {
+        self.0.get(feature).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Tracked features with no snippets exercising them at all.
+    pub fn uncovered(&self) -> Vec<&'static str> {
+        TRACKED_FEATURES
+            .iter()
+            .copied()
+            .filter(|f| self.0.get(*f).is_none_or(Vec::is_empty))
+            .collect()
+    }
+}
+
+/// Extracts the set of features enabled by a snippet's `#![feature(...)]`
+/// inner attributes (there can be more than one such attribute line).
+pub fn enabled_features(source: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    for line in source.lines() {
+        let line = line.trim();
+        let Some(rest) = line.strip_prefix("#![feature(") else {
+            continue;
+        };
+        let Some(list) = rest.strip_suffix(")]") else {
+            continue;
+        };
+        for name in list.split(',') {
+            let name = name.trim();
+            if !name.is_empty() {
+                features.push(name.to_string());
+            }
+        }
+    }
+    features
+}
+
+/// Builds the reverse feature -> snippets index across the whole corpus.
+pub fn build_index(entries: &[(Entry, String)]) -> FeatureIndex {
+    let mut map: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for (entry, source) in entries {
+        for feature in enabled_features(source) {
+            map.entry(feature).or_default().push(entry.path.clone());
+        }
+    }
+    FeatureIndex(map)
+}
+
+/// A snippet can optionally declare which features it means to exercise via
+/// a `// features: a, b` header line, as a cross-check against what its
+/// `#![feature(...)]` attributes actually enable. Returns entries where the
+/// two sets disagree.
+pub fn find_mismatches(entries: &[(Entry, String)]) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for (entry, source) in entries {
+        let Some(declared) = declared_features(&entry.header) else {
+            continue;
+        };
+        let mut actual = enabled_features(source);
+        let mut declared_sorted = declared.clone();
+        actual.sort();
+        declared_sorted.sort();
+        if actual != declared_sorted {
+            mismatches.push(Mismatch {
+                path: entry.path.clone(),
+                declared,
+                actual,
+            });
+        }
+    }
+    mismatches
+}
+
+fn declared_features(header: &Header) -> Option<Vec<String>> {
+    let value = header.get("features")?;
+    Some(
+        value
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(path: &str, source: &str) -> (Entry, String) {
+        (
+            Entry {
+                path: PathBuf::from(path),
+                header: Header::parse(source),
+                affected: None,
+            },
+            source.to_string(),
+        )
+    }
+
+    #[test]
+    fn extracts_a_single_feature_attribute() {
+        assert_eq!(
+            enabled_features("#![feature(repr_simd)]\nfn main() {}"),
+            vec!["repr_simd".to_string()]
+        );
+    }
+
+    #[test]
+    fn extracts_multiple_features_from_one_attribute() {
+        assert_eq!(
+            enabled_features("#![feature(sized_hierarchy, non_lifetime_binders)]\n"),
+            vec!["sized_hierarchy".to_string(), "non_lifetime_binders".to_string()]
+        );
+    }
+
+    #[test]
+    fn extracts_features_from_multiple_attribute_lines() {
+        assert_eq!(
+            enabled_features("#![feature(repr_simd)]\n#![feature(transmutability)]\n"),
+            vec!["repr_simd".to_string(), "transmutability".to_string()]
+        );
+    }
+
+    #[test]
+    fn build_index_maps_feature_to_snippets() {
+        let entries = vec![
+            entry("a.rs", "#![feature(repr_simd)]\n"),
+            entry("b.rs", "#![feature(repr_simd)]\n"),
+            entry("c.rs", "#![feature(sized_hierarchy)]\n"),
+        ];
+        let index = build_index(&entries);
+        assert_eq!(
+            index.snippets_for("repr_simd"),
+            &[PathBuf::from("a.rs"), PathBuf::from("b.rs")]
+        );
+        assert_eq!(index.snippets_for("sized_hierarchy"), &[PathBuf::from("c.rs")]);
+        assert_eq!(index.snippets_for("min_generic_const_args"), &[] as &[PathBuf]);
+    }
+
+    #[test]
+    fn uncovered_lists_tracked_features_with_no_snippets() {
+        let entries = vec![entry("a.rs", "#![feature(repr_simd)]\n")];
+        let index = build_index(&entries);
+        let uncovered = index.uncovered();
+        assert!(!uncovered.contains(&"repr_simd"));
+        assert!(uncovered.contains(&"sized_hierarchy"));
+    }
+
+    #[test]
+    fn find_mismatches_flags_declared_set_disagreeing_with_actual() {
+        let entries = vec![
+            entry(
+                "a.rs",
+                "// features: repr_simd\n#![feature(repr_simd)]\n",
+            ),
+            entry(
+                "b.rs",
+                "// features: repr_simd\n#![feature(sized_hierarchy)]\n",
+            ),
+        ];
+        let mismatches = find_mismatches(&entries);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, PathBuf::from("b.rs"));
+        assert_eq!(mismatches[0].declared, vec!["repr_simd".to_string()]);
+        assert_eq!(mismatches[0].actual, vec!["sized_hierarchy".to_string()]);
+    }
+
+    #[test]
+    fn find_mismatches_skips_snippets_without_a_declared_features_header() {
+        let entries = vec![entry("a.rs", "#![feature(repr_simd)]\n")];
+        assert!(find_mismatches(&entries).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Parsing for the leading `//`-comment header that each corpus snippet carries,
+//! e.g. `// affected versions: 1.86-1.93`.
+//!
+//! Snippets are not consistent about the space after `//` (`//affected versions: ...`
+//! vs `// affected versions: ...`), so parsing is whitespace-tolerant.
+
+use std::collections::BTreeMap;
+
+/// One line of the original leading comment block, kept so [`Header::rewrite`]
+/// can reproduce it faithfully: a `key: value` field (looked up from the
+/// current field map at rewrite time, so edits via [`Header::set`] show up),
+/// or a plain remark line reproduced byte-for-byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HeaderLine {
+    Field(String),
+    Remark(String),
+}
+
+/// The `key: value` pairs found in a snippet's leading comment block, in the
+/// order they appeared, plus enough of the original layout to rewrite the
+/// header without losing non-field remark lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Header {
+    fields: BTreeMap<String, String>,
+    order: Vec<String>,
+    lines: Vec<HeaderLine>,
+}
+
+impl Header {
+    /// Parses every leading `//` line of `source` as a `key: value` pair.
+    /// Parsing stops at the first non-comment line. A comment line with no
+    /// `:` separator is treated as a plain remark rather than a field — it's
+    /// still part of the header, and is preserved verbatim by [`Header::rewrite`].
+    pub fn parse(source: &str) -> Header {
+        let mut header = Header::default();
+        for line in source.lines() {
+            let Some(rest) = line.strip_prefix("//") else {
+                break;
+            };
+            let rest = rest.trim_start();
+            let Some((key, value)) = rest.split_once(':') else {
+                header.lines.push(HeaderLine::Remark(line.to_string()));
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim().to_string();
+            if !header.fields.contains_key(&key) {
+                header.order.push(key.clone());
+            }
+            header.fields.insert(key.clone(), value);
+            header.lines.push(HeaderLine::Field(key));
+        }
+        header
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        if !self.fields.contains_key(key) {
+            self.order.push(key.to_string());
+        }
+        self.fields.insert(key.to_string(), value.into());
+    }
+
+    /// Rewrites `source`'s leading comment block to reflect this header,
+    /// preserving the rest of the file untouched. Existing field lines are
+    /// updated in place, remark lines are reproduced verbatim, and fields
+    /// added via [`Header::set`] that weren't in the original header are
+    /// appended at the end of the block.
+    pub fn rewrite(&self, source: &str) -> String {
+        let mut body_start = 0;
+        for line in source.lines() {
+            if line.strip_prefix("//").is_none() {
+                break;
+            }
+            body_start += line.len() + 1;
+        }
+        let body = source.get(body_start.min(source.len())..).unwrap_or("");
+
+        let mut out = String::new();
+        for line in &self.lines {
+            match line {
+                HeaderLine::Field(key) => {
+                    out.push_str("// ");
+                    out.push_str(key);
+                    out.push_str(": ");
+                    out.push_str(&self.fields[key]);
+                    out.push('\n');
+                }
+                HeaderLine::Remark(raw) => {
+                    out.push_str(raw);
+                    out.push('\n');
+                }
+            }
+        }
+        for key in &self.order {
+            if self.lines.contains(&HeaderLine::Field(key.clone())) {
+                continue;
+            }
+            out.push_str("// ");
+            out.push_str(key);
+            out.push_str(": ");
+            out.push_str(&self.fields[key]);
+            out.push('\n');
+        }
+        out.push_str(body);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_with_and_without_leading_space() {
+        let h = Header::parse("//affected versions: 1.84-1.95\ntrait Foo {}");
+        assert_eq!(h.get("affected versions"), Some("1.84-1.95"));
+
+        let h = Header::parse("// affected versions: 1.48-1.92\nfn main() {}");
+        assert_eq!(h.get("affected versions"), Some("1.48-1.92"));
+    }
+
+    #[test]
+    fn stops_at_first_non_comment_line() {
+        let h = Header::parse("// affected versions: 1.95\n#![feature(repr_simd)]\nfn main() {}");
+        assert_eq!(h.get("affected versions"), Some("1.95"));
+        assert_eq!(h.get("feature"), None);
+    }
+
+    #[test]
+    fn rewrite_replaces_only_the_header() {
+        let mut h = Header::parse("// affected versions: 1.95\nfn main() {}\n");
+        h.set("affected versions", "1.95-1.96");
+        assert_eq!(
+            h.rewrite("// affected versions: 1.95\nfn main() {}\n"),
+            "// affected versions: 1.95-1.96\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn set_appends_new_fields_after_existing_ones() {
+        let mut h = Header::parse("// affected versions: 1.95\nfn main() {}\n");
+        h.set("category", "unsound");
+        assert_eq!(
+            h.rewrite("// affected versions: 1.95\nfn main() {}\n"),
+            "// affected versions: 1.95\n// category: unsound\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn rewrite_preserves_plain_remark_lines() {
+        let source =
+            "// affected versions: 1.95\n// this one is nasty, see upstream tracking issue\nfn main() {}\n";
+        let mut h = Header::parse(source);
+        h.set("affected versions", "1.95-1.96");
+        assert_eq!(
+            h.rewrite(source),
+            "// affected versions: 1.95-1.96\n// this one is nasty, see upstream tracking issue\nfn main() {}\n"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! `rice` — tooling for the RICE corpus of rustc ICE-triggering snippets.
+//!
+//! Turns the corpus from a static pile of files with free-form
+//! `// affected versions: ...` comments into a self-validating regression
+//! database: every snippet's recorded range can be re-derived by actually
+//! compiling it under the toolchains that bracket that range.
+
+mod annotation;
+mod bisect;
+mod checker;
+mod dedup;
+mod features;
+mod header;
+mod manifest;
+mod outcome;
+mod release_table;
+mod runner;
+mod signature;
+mod soundness;
+mod version_range;
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: rice <revalidate|dedup|soundness|features|check> \
+[--toolchain TOOLCHAIN] [--feature NAME]";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("revalidate") => revalidate(),
+        Some("dedup") => run_dedup(),
+        Some("soundness") => run_soundness(args.collect()),
+        Some("features") => run_features(args.collect()),
+        Some("check") => run_check(),
+        Some(other) => {
+            eprintln!("rice: unknown subcommand `{other}`");
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Re-runs every corpus snippet's recorded `affected versions` range through
+/// the bisector and updates the header in place when the observed range has
+/// drifted.
+fn revalidate() -> ExitCode {
+    let root = PathBuf::from(".");
+    let entries = match manifest::load_corpus(&root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("rice: failed to load corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut drifted = 0;
+    for entry in &entries {
+        let name = entry.path.display();
+        match manifest::revalidate(entry) {
+            Ok(Some(report)) => {
+                if let Some(observed) = report.updated {
+                    println!(
+                        "{name}: recorded range diverged ({}), updated to {observed}",
+                        report.boundary
+                    );
+                    drifted += 1;
+                } else {
+                    println!("{name}: matches recorded range ({})", report.boundary);
+                }
+            }
+            Ok(None) => println!("{name}: no recorded range to check"),
+            Err(err) => eprintln!("{name}: {err}"),
+        }
+    }
+
+    println!("{drifted}/{} snippets updated", entries.len());
+    ExitCode::SUCCESS
+}
+
+/// Buckets the corpus by normalized panic/query-stack signature and prints
+/// each bucket with more than one member, so duplicate ICEs are easy to
+/// spot.
+fn run_dedup() -> ExitCode {
+    let root = PathBuf::from(".");
+    let entries = match manifest::load_corpus(&root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("rice: failed to load corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let buckets = match dedup::dedup(&entries) {
+        Ok(buckets) => buckets,
+        Err(err) => {
+            eprintln!("rice: dedup failed: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for bucket in &buckets {
+        if bucket.members.len() < 2 {
+            continue;
+        }
+        println!("signature {:?}:", bucket.signature);
+        for member in &bucket.members {
+            println!("  {}", member.display());
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Runs every `category: unsound` snippet through [`soundness::verify`] and
+/// reports which soundness holes are still open.
+fn run_soundness(raw_args: Vec<String>) -> ExitCode {
+    let toolchain = toolchain_flag(&raw_args).unwrap_or_else(|| "stable".to_string());
+
+    let root = PathBuf::from(".");
+    let entries = match manifest::load_corpus(&root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("rice: failed to load corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for entry in &entries {
+        if soundness::Category::of(&entry.header) != Some(soundness::Category::Unsound) {
+            continue;
+        }
+        let name = entry.path.display();
+        match soundness::verify(&entry.path, &toolchain) {
+            Ok(state) => println!("{name}: {state:?}"),
+            Err(err) => eprintln!("{name}: {err}"),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Pulls a `--toolchain VALUE` pair out of a raw argument list.
+fn toolchain_flag(args: &[String]) -> Option<String> {
+    flag_value(args, "--toolchain")
+}
+
+fn flag_value(args: &[String], flag: &str) -> Option<String> {
+    let idx = args.iter().position(|a| a == flag)?;
+    args.get(idx + 1).cloned()
+}
+
+/// Builds the feature-gate coverage index. With `--feature NAME`, prints
+/// just the snippets that gate depends on; otherwise prints the full index
+/// plus any header/attribute mismatches and tracked features with zero
+/// coverage.
+fn run_features(raw_args: Vec<String>) -> ExitCode {
+    let root = PathBuf::from(".");
+    let entries = match manifest::load_corpus(&root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("rice: failed to load corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let with_source: Vec<(manifest::Entry, String)> = match entries
+        .into_iter()
+        .map(|entry| {
+            let source = std::fs::read_to_string(&entry.path)?;
+            Ok((entry, source))
+        })
+        .collect::<std::io::Result<_>>()
+    {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("rice: failed to read corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let index = features::build_index(&with_source);
+
+    if let Some(feature) = flag_value(&raw_args, "--feature") {
+        for path in index.snippets_for(&feature) {
+            println!("{}", path.display());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    for feature in features::TRACKED_FEATURES {
+        println!("{feature}:");
+        for path in index.snippets_for(feature) {
+            println!("  {}", path.display());
+        }
+    }
+
+    let uncovered = index.uncovered();
+    if !uncovered.is_empty() {
+        println!("uncovered: {}", uncovered.join(", "));
+    }
+
+    for mismatch in features::find_mismatches(&with_source) {
+        println!(
+            "{}: declared [{}] != actual [{}]",
+            mismatch.path.display(),
+            mismatch.declared.join(", "),
+            mismatch.actual.join(", ")
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Diffs every annotated snippet's expected error code, compiler phase, and
+/// current behavior against what actually happens when it's compiled.
+fn run_check() -> ExitCode {
+    let root = PathBuf::from(".");
+    let entries = match manifest::load_corpus(&root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("rice: failed to load corpus: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for entry in &entries {
+        let annotation = annotation::Annotation::parse(&entry.header);
+        if !annotation.is_present() {
+            continue;
+        }
+
+        let observed = match checker::observe(&entry.path) {
+            Ok(observed) => observed,
+            Err(err) => {
+                eprintln!("{}: {err}", entry.path.display());
+                continue;
+            }
+        };
+
+        let mismatches = checker::diff(&annotation, &observed);
+        if mismatches.is_empty() {
+            println!("{}: matches annotation", entry.path.display());
+            continue;
+        }
+        println!("{}:", entry.path.display());
+        for mismatch in mismatches {
+            println!(
+                "  {}: expected {}, observed {}",
+                mismatch.field, mismatch.expected, mismatch.observed
+            );
+        }
+    }
+    ExitCode::SUCCESS
+}
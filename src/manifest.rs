@@ -0,0 +1,91 @@
+//! Ties the corpus on disk together with the structured `affected versions`
+//! metadata: loading it, re-running every snippet against its recorded
+//! range, and writing back an observed range when it has drifted.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bisect::{bisect, BisectResult};
+use crate::header::Header;
+use crate::release_table::STABLE_RELEASES;
+use crate::version_range::VersionRange;
+
+pub const CORPUS_DIR: &str = "zero_days";
+
+/// One snippet's parsed header plus its location on disk.
+pub struct Entry {
+    pub path: PathBuf,
+    pub header: Header,
+    pub affected: Option<VersionRange>,
+}
+
+/// Loads every `.rs` file in [`CORPUS_DIR`], parsing its header.
+pub fn load_corpus(root: &Path) -> std::io::Result<Vec<Entry>> {
+    let dir = root.join(CORPUS_DIR);
+    let mut entries = Vec::new();
+    for file in fs::read_dir(dir)? {
+        let path = file?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path)?;
+        let header = Header::parse(&source);
+        let affected = header.get("affected versions").and_then(VersionRange::parse);
+        entries.push(Entry {
+            path,
+            header,
+            affected,
+        });
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// What bisecting `entry` against the newest known stable release found.
+pub struct Revalidation {
+    /// Human-readable description of the exact boundary the bisector
+    /// landed on (which version is clean, which is the first/last to ICE).
+    pub boundary: String,
+    /// The corrected range, if it diverged from what was recorded (and was
+    /// written back to the file).
+    pub updated: Option<VersionRange>,
+}
+
+/// Re-validates `entry`'s recorded `affected versions` range by bisecting
+/// from its recorded lower bound up through the newest known stable release
+/// — not just up to its recorded upper bound, since that upper bound is
+/// exactly the thing being checked for drift — and rewrites the header in
+/// place if the observed boundary diverges from what was recorded.
+///
+/// Both an ascending transition (clean, then ICEs — an open-ended range
+/// that has grown) and a descending one (ICEs, then fixed — a closed range
+/// whose true upper bound was never bisected) are accounted for.
+pub fn revalidate(entry: &Entry) -> std::io::Result<Option<Revalidation>> {
+    let Some(range) = entry.affected else {
+        return Ok(None);
+    };
+
+    let newest = STABLE_RELEASES.last().copied().unwrap_or(range.lower());
+    let result = bisect(&entry.path, range.lower(), newest)?;
+    let boundary = result.to_string();
+    let observed = match result {
+        BisectResult::Appeared { first_ice, .. } => VersionRange::OpenEnded(first_ice),
+        BisectResult::Fixed { last_ice, .. } => VersionRange::Inclusive(range.lower(), last_ice),
+        BisectResult::AlwaysIce => VersionRange::OpenEnded(range.lower()),
+        // Neither end reproduces the ICE: nothing to safely bisect from.
+        BisectResult::NeverIce => return Ok(Some(Revalidation { boundary, updated: None })),
+    };
+
+    if observed == range {
+        return Ok(Some(Revalidation { boundary, updated: None }));
+    }
+
+    let mut header = entry.header.clone();
+    header.set("affected versions", observed.to_string());
+    let source = fs::read_to_string(&entry.path)?;
+    fs::write(&entry.path, header.rewrite(&source))?;
+    Ok(Some(Revalidation {
+        boundary,
+        updated: Some(observed),
+    }))
+}
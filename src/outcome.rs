@@ -0,0 +1,42 @@
+//! The classification a single (snippet, toolchain) compilation run can
+//! produce.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// rustc panicked: an internal compiler error.
+    Ice,
+    /// rustc exited non-zero with ordinary diagnostics, no panic.
+    CompileError,
+    /// rustc accepted the snippet.
+    Clean,
+}
+
+impl Outcome {
+    /// Classifies a completed `rustc` invocation from its exit status and
+    /// captured stderr.
+    pub fn classify(success: bool, stderr: &str) -> Outcome {
+        if stderr.contains("internal compiler error")
+            || stderr.contains("RUST_BACKTRACE=")
+            || stderr.contains("thread 'rustc' panicked")
+        {
+            Outcome::Ice
+        } else if success {
+            Outcome::Clean
+        } else {
+            Outcome::CompileError
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Outcome::Ice => "ICE",
+            Outcome::CompileError => "CompileError",
+            Outcome::Clean => "Clean",
+        };
+        f.write_str(s)
+    }
+}
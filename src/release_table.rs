@@ -0,0 +1,109 @@
+//! A shipped table of stable rustc releases, used to order candidate
+//! toolchains for bisection without needing network access to query them.
+//!
+//! This only needs to be kept roughly current: [`installed_releases`]
+//! intersects it with whatever `rustup toolchain list` reports is actually
+//! installed, so an entry here that nobody has installed just never gets
+//! chosen as a bisection candidate instead of blowing up the bisector.
+
+use std::process::Command;
+
+use crate::version_range::Version;
+
+/// Stable releases in ascending order, newest last. Update when rustc cuts a
+/// new stable release that the corpus needs to bisect against.
+pub const STABLE_RELEASES: &[Version] = &[
+    Version { major: 1, minor: 48 },
+    Version { major: 1, minor: 60 },
+    Version { major: 1, minor: 70 },
+    Version { major: 1, minor: 75 },
+    Version { major: 1, minor: 80 },
+    Version { major: 1, minor: 84 },
+    Version { major: 1, minor: 85 },
+    Version { major: 1, minor: 86 },
+    Version { major: 1, minor: 87 },
+    Version { major: 1, minor: 88 },
+    Version { major: 1, minor: 89 },
+    Version { major: 1, minor: 90 },
+    Version { major: 1, minor: 91 },
+    Version { major: 1, minor: 92 },
+    Version { major: 1, minor: 93 },
+    Version { major: 1, minor: 94 },
+    Version { major: 1, minor: 95 },
+];
+
+/// Returns every release in [`STABLE_RELEASES`] that falls within `lo..=hi`,
+/// ascending. Used to build the candidate list a bisection walks.
+pub fn releases_between(lo: Version, hi: Version) -> Vec<Version> {
+    STABLE_RELEASES
+        .iter()
+        .copied()
+        .filter(|v| *v >= lo && *v <= hi)
+        .collect()
+}
+
+/// Runs `rustup toolchain list` and returns the [`STABLE_RELEASES`] entries
+/// that are actually installed, so the bisector never picks a candidate that
+/// can only fail with "toolchain not installed" rather than a real outcome.
+pub fn installed_releases() -> std::io::Result<Vec<Version>> {
+    let output = Command::new("rustup").arg("toolchain").arg("list").output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let installed: Vec<Version> = stdout.lines().filter_map(parse_toolchain_name).collect();
+    Ok(STABLE_RELEASES
+        .iter()
+        .copied()
+        .filter(|v| installed.contains(v))
+        .collect())
+}
+
+/// Pulls the `major.minor` version out of a `rustup toolchain list` line,
+/// e.g. `1.86.0-x86_64-unknown-linux-gnu (default)` -> `1.86`. Lines for
+/// non-versioned toolchains (`stable-...`, `nightly-...`) don't parse and
+/// are skipped.
+fn parse_toolchain_name(line: &str) -> Option<Version> {
+    let name = line.split_whitespace().next()?;
+    let mut parts = name.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.split('-').next()?.parse().ok()?;
+    Some(Version { major, minor })
+}
+
+/// Intersects [`releases_between`] with [`installed_releases`], i.e. the
+/// candidates a bisection can actually run against.
+pub fn available_releases_between(lo: Version, hi: Version) -> std::io::Result<Vec<Version>> {
+    let installed = installed_releases()?;
+    Ok(releases_between(lo, hi)
+        .into_iter()
+        .filter(|v| installed.contains(v))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases_between_filters_inclusive_range() {
+        let lo = Version { major: 1, minor: 85 };
+        let hi = Version { major: 1, minor: 88 };
+        assert_eq!(
+            releases_between(lo, hi),
+            vec![
+                Version { major: 1, minor: 85 },
+                Version { major: 1, minor: 86 },
+                Version { major: 1, minor: 87 },
+                Version { major: 1, minor: 88 },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_toolchain_name_reads_major_minor_ignoring_patch_and_target() {
+        assert_eq!(
+            parse_toolchain_name("1.86.0-x86_64-unknown-linux-gnu (default)"),
+            Some(Version { major: 1, minor: 86 })
+        );
+        assert_eq!(parse_toolchain_name("stable-x86_64-unknown-linux-gnu"), None);
+        assert_eq!(parse_toolchain_name("nightly-x86_64-unknown-linux-gnu"), None);
+    }
+}
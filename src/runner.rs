@@ -0,0 +1,77 @@
+//! Compiles a single corpus snippet under a specific rustup toolchain and
+//! captures the result.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crate::outcome::Outcome;
+use crate::version_range::Version;
+
+/// The result of compiling one snippet under one toolchain.
+pub struct CompileResult {
+    pub outcome: Outcome,
+    pub stderr: String,
+}
+
+/// Compiles `snippet` with `rustc` under the given rustup toolchain, emitting
+/// to a throwaway location since the corpus only cares about the diagnostic
+/// outcome.
+///
+/// A toolchain rustup doesn't have installed isn't a real compile outcome —
+/// it's an environment problem — so it's surfaced as an `Err` rather than
+/// folded into [`Outcome::CompileError`], which would otherwise make a
+/// missing toolchain indistinguishable from rustc legitimately rejecting the
+/// snippet.
+pub fn compile_with(snippet: &Path, version: Version) -> io::Result<CompileResult> {
+    let out_dir = std::env::temp_dir().join("rice-runner");
+    std::fs::create_dir_all(&out_dir)?;
+    let out_file = out_dir.join(
+        snippet
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .replace(['*', '.'], "_"),
+    );
+
+    let output = Command::new("rustup")
+        .arg("run")
+        .arg(version.to_string())
+        .arg("rustc")
+        .arg(snippet)
+        .arg("-o")
+        .arg(&out_file)
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() && is_missing_toolchain(&stderr) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("toolchain {version} is not installed (run `rustup toolchain install {version}`)"),
+        ));
+    }
+
+    Ok(CompileResult {
+        outcome: Outcome::classify(output.status.success(), &stderr),
+        stderr,
+    })
+}
+
+/// Recognizes rustup's own "no such toolchain" message, as distinct from
+/// rustc rejecting the snippet.
+fn is_missing_toolchain(stderr: &str) -> bool {
+    stderr.contains("is not installed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_rustups_missing_toolchain_message() {
+        assert!(is_missing_toolchain(
+            "error: toolchain '1.48-x86_64-unknown-linux-gnu' is not installed"
+        ));
+        assert!(!is_missing_toolchain("error[E0308]: mismatched types"));
+    }
+}
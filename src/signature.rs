@@ -0,0 +1,256 @@
+//! Reduces a captured rustc panic (and its `query stack during panic` trace)
+//! to a canonical signature so that snippets crashing the compiler at the
+//! same place can be bucketed together.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use scrub as patterns;
+
+/// How many query-stack frames (beyond the panic location) contribute to the
+/// signature. Past this, frames tend to be generic plumbing shared by
+/// unrelated ICEs, which would cause false merges.
+const MAX_FRAMES: usize = 4;
+
+/// A panic/query-stack class. Two snippets with equal signatures are
+/// considered the same underlying ICE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Signature(u64);
+
+/// What kind of crash a captured rustc run represents, used to keep
+/// panics and delayed-bug/fulfillment-error exits in separate signature
+/// classes even when their surrounding text looks similar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashClass {
+    /// A genuine `thread 'rustc' panicked` ICE.
+    Panic,
+    /// `stashed diagnostic` / delayed-bug style exit: rustc noticed
+    /// something was wrong and chose to keep going before erroring out
+    /// (e.g. the fulfillment-error path for the object-safety
+    /// associated-type case), rather than panicking outright.
+    DelayedBug,
+    /// Not a crash at all: a plain `error[E####]` rejection.
+    CleanRejection,
+}
+
+impl CrashClass {
+    fn classify(stderr: &str) -> CrashClass {
+        if stderr.contains("thread 'rustc' panicked") || stderr.contains("internal compiler error")
+        {
+            CrashClass::Panic
+        } else if stderr.contains("encountered errors") || stderr.contains("stashed diagnostic") {
+            CrashClass::DelayedBug
+        } else {
+            CrashClass::CleanRejection
+        }
+    }
+}
+
+/// Normalizes and hashes a captured rustc stderr into a [`Signature`].
+///
+/// Normalization strips everything that varies run-to-run but not
+/// crash-to-crash: absolute file paths, line/column numbers, memory
+/// addresses, monomorphization hashes (`::habcdef0123456789`), and `DefId`
+/// integers. What's left is the crash class plus the first [`MAX_FRAMES`]
+/// query-stack frame names and the panic location's file name.
+pub fn signature_of(stderr: &str) -> Signature {
+    let class = CrashClass::classify(stderr);
+    let frames = query_stack_frames(stderr);
+    let panic_site = panic_location(stderr);
+
+    let mut hasher = DefaultHasher::new();
+    std::mem::discriminant(&class).hash(&mut hasher);
+    panic_site.hash(&mut hasher);
+    for frame in frames.iter().take(MAX_FRAMES) {
+        normalize_frame(frame).hash(&mut hasher);
+    }
+    Signature(hasher.finish())
+}
+
+/// Extracts `#N [query_name] ...` lines from a `query stack during panic`
+/// trace, in the order rustc printed them (innermost first).
+pub(crate) fn query_stack_frames(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let rest = line.strip_prefix('#')?;
+            let (_num, rest) = rest.split_once(' ')?;
+            let rest = rest.trim_start_matches('[').split(']').next()?;
+            Some(rest.to_string())
+        })
+        .collect()
+}
+
+/// Pulls the file name (`file.rs`) out of the
+/// `panicked at compiler/.../file.rs:LL:CC` line, ignoring the directories
+/// and position.
+fn panic_location(stderr: &str) -> Option<String> {
+    let line = stderr.lines().find(|l| l.contains("panicked at"))?;
+    let after = line.split("panicked at").nth(1)?;
+    let path = after.trim().split(':').next()?;
+    path.rsplit('/').next().map(str::to_string)
+}
+
+/// Strips the parts of a query-stack frame name that vary between otherwise
+/// identical crashes: monomorphization hashes, `DefId(..)` integers, and
+/// memory addresses.
+fn normalize_frame(frame: &str) -> String {
+    let mut out = patterns::strip_mono_hashes(frame);
+    out = patterns::strip_def_ids(&out);
+    out = patterns::strip_addresses(&out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_panic_vs_delayed_bug_vs_clean() {
+        assert_eq!(
+            CrashClass::classify("thread 'rustc' panicked at 'oops'"),
+            CrashClass::Panic
+        );
+        assert_eq!(
+            CrashClass::classify("error: internal compiler error"),
+            CrashClass::Panic
+        );
+        assert_eq!(
+            CrashClass::classify("error: the compiler unexpectedly encountered errors"),
+            CrashClass::DelayedBug
+        );
+        assert_eq!(
+            CrashClass::classify("error[E0038]: the trait cannot be made into an object"),
+            CrashClass::CleanRejection
+        );
+    }
+
+    #[test]
+    fn extracts_query_stack_frames_in_order() {
+        let stderr = "query stack during panic:\n#0 [type_of] computing type of `Foo`\n#1 [check_well_formed] checking that `Foo` is well-formed\n";
+        assert_eq!(
+            query_stack_frames(stderr),
+            vec!["type_of".to_string(), "check_well_formed".to_string()]
+        );
+    }
+
+    #[test]
+    fn extracts_panic_location_file_name_only() {
+        let stderr = "thread 'rustc' panicked at compiler/rustc_middle/src/ty/mod.rs:123:45:\nsome message\n";
+        assert_eq!(panic_location(stderr), Some("mod.rs".to_string()));
+    }
+
+    #[test]
+    fn signatures_ignore_addresses_and_hashes_but_not_frame_identity() {
+        let a = "thread 'rustc' panicked at compiler/x.rs:1:1:\nquery stack during panic:\n#0 [type_of::hdeadbeefcafebabe] at 0x7f0000000000\n";
+        let b = "thread 'rustc' panicked at compiler/x.rs:9:9:\nquery stack during panic:\n#0 [type_of::h1111111111111111] at 0x7fffffffffff\n";
+        assert_eq!(signature_of(a), signature_of(b));
+
+        let different_frame = "thread 'rustc' panicked at compiler/x.rs:1:1:\nquery stack during panic:\n#0 [mir_built] at 0x7f0000000000\n";
+        assert_ne!(signature_of(a), signature_of(different_frame));
+    }
+}
+
+/// Small hand-rolled substring scrubbers, kept local rather than pulling in
+/// a regex dependency for three fixed patterns.
+mod scrub {
+    /// Removes `::h` followed by exactly 16 hex digits (a monomorphization
+    /// hash suffix), e.g. `foo::h1a2b3c4d5e6f7081`. A shorter run of hex
+    /// digits after `::h` isn't a hash suffix and is left alone.
+    pub fn strip_mono_hashes(s: &str) -> String {
+        strip_exact_pattern(s, "::h", |c| c.is_ascii_hexdigit(), 16)
+    }
+
+    /// Removes `DefId(N:M)` occurrences down to the bare literal `DefId`.
+    pub fn strip_def_ids(s: &str) -> String {
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(idx) = rest.find("DefId(") {
+            out.push_str(&rest[..idx]);
+            out.push_str("DefId");
+            let after = &rest[idx + "DefId(".len()..];
+            match after.find(')') {
+                Some(close) => rest = &after[close + 1..],
+                None => {
+                    rest = "";
+                    break;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Removes `0x` followed by hex digits (a memory address).
+    pub fn strip_addresses(s: &str) -> String {
+        strip_pattern(s, "0x", |c| c.is_ascii_hexdigit())
+    }
+
+    /// Finds occurrences of `marker` followed by any run of chars matching
+    /// `is_digit`, and drops the whole matched span (marker included).
+    fn strip_pattern(s: &str, marker: &str, is_digit: impl Fn(char) -> bool) -> String {
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(idx) = rest.find(marker) {
+            out.push_str(&rest[..idx]);
+            let after = &rest[idx + marker.len()..];
+            let digit_len = after.char_indices().take_while(|(_, c)| is_digit(*c)).count();
+            if digit_len == 0 {
+                out.push_str(marker);
+                rest = after;
+            } else {
+                rest = &after[digit_len..];
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Like [`strip_pattern`], but only strips when exactly `exact_digits`
+    /// matching chars follow `marker` — a shorter or longer run is left
+    /// untouched, since it isn't the fixed-width token being targeted.
+    fn strip_exact_pattern(
+        s: &str,
+        marker: &str,
+        is_digit: impl Fn(char) -> bool,
+        exact_digits: usize,
+    ) -> String {
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(idx) = rest.find(marker) {
+            out.push_str(&rest[..idx]);
+            let after = &rest[idx + marker.len()..];
+            let digit_len = after.char_indices().take_while(|(_, c)| is_digit(*c)).count();
+            if digit_len == exact_digits {
+                rest = &after[digit_len..];
+            } else {
+                out.push_str(marker);
+                rest = after;
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn strips_mono_hash_suffix() {
+            assert_eq!(strip_mono_hashes("foo::h1a2b3c4d5e6f7081"), "foo");
+            assert_eq!(strip_mono_hashes("foo::hbar"), "foo::hbar");
+        }
+
+        #[test]
+        fn strips_def_id_down_to_bare_name() {
+            assert_eq!(strip_def_ids("type_of(DefId(0:3 ~ foo))"), "type_of(DefId)");
+        }
+
+        #[test]
+        fn strips_memory_addresses() {
+            assert_eq!(strip_addresses("at 0x7f3a9c001230 in"), "at  in");
+        }
+    }
+}
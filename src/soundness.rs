@@ -0,0 +1,88 @@
+//! Verification mode for the `unsound` category: snippets that aren't ICEs
+//! at all but soundness holes (an object-safe trait with an unchecked
+//! associated-type bound, an opaque-type lifetime leak, etc.), where the
+//! interesting question is not "did it compile" but "does the binary it
+//! produces actually exhibit UB."
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::header::Header;
+
+/// The category tag a snippet's header can carry. Only `Unsound` snippets
+/// get run through [`verify`]; everything else is a plain ICE as before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Unsound,
+}
+
+impl Category {
+    pub fn of(header: &Header) -> Option<Category> {
+        match header.get("category") {
+            Some("unsound") => Some(Category::Unsound),
+            _ => None,
+        }
+    }
+}
+
+/// The state of a live soundness hole, as observed by actually compiling and
+/// running the snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundnessState {
+    /// The fix landed: the snippet is now correctly rejected at compile
+    /// time.
+    Rejected,
+    /// The hole is still open: the snippet compiles, and Miri (or the
+    /// snippet's own crafted assertion) catches the resulting UB.
+    AcceptedButUb,
+    /// The hole is open and silent: the snippet compiles, runs to
+    /// completion, and nothing flags the miscompile.
+    AcceptedClean,
+}
+
+/// Compiles `snippet` with the given toolchain; if it wrongly succeeds, runs
+/// `rustup run TOOLCHAIN miri run SNIPPET` on the snippet's source and
+/// classifies the result. The binary from the compile step is not executed.
+/// A non-zero Miri exit, or `Undefined Behavior` in its stderr, counts as
+/// flagged.
+pub fn verify(snippet: &Path, toolchain: &str) -> std::io::Result<SoundnessState> {
+    let out_dir = std::env::temp_dir().join("rice-soundness");
+    std::fs::create_dir_all(&out_dir)?;
+    let out_file = out_dir.join(
+        snippet
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .replace(['*', '.'], "_"),
+    );
+
+    let compile = Command::new("rustup")
+        .arg("run")
+        .arg(toolchain)
+        .arg("rustc")
+        .arg(snippet)
+        .arg("-o")
+        .arg(&out_file)
+        .output()?;
+
+    if !compile.status.success() {
+        return Ok(SoundnessState::Rejected);
+    }
+
+    let miri = Command::new("rustup")
+        .arg("run")
+        .arg(toolchain)
+        .arg("miri")
+        .arg("run")
+        .arg(snippet)
+        .output()?;
+
+    let flagged = !miri.status.success()
+        || String::from_utf8_lossy(&miri.stderr).contains("Undefined Behavior");
+
+    Ok(if flagged {
+        SoundnessState::AcceptedButUb
+    } else {
+        SoundnessState::AcceptedClean
+    })
+}
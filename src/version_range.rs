@@ -0,0 +1,121 @@
+//! Structured form of the `affected versions` header field.
+//!
+//! The corpus writes ranges three ways: a single version (`1.95`), an
+//! inclusive range (`1.86-1.93`), and an open-ended range for nightlies that
+//! are still affected as of the recorded upper bound (`1.89-`).
+
+use std::fmt;
+
+/// A `major.minor` rustc version, e.g. `1.93`. The corpus never needs patch
+/// versions, so they aren't modeled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Option<Version> {
+        let s = s.trim();
+        let (major, minor) = s.split_once('.')?;
+        Some(Version {
+            major: major.parse().ok()?,
+            minor: minor.parse().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionRange {
+    Single(Version),
+    Inclusive(Version, Version),
+    OpenEnded(Version),
+}
+
+impl VersionRange {
+    /// Parses an `affected versions` value such as `1.86-1.93`, `1.95`, or
+    /// `1.89-`.
+    pub fn parse(s: &str) -> Option<VersionRange> {
+        let s = s.trim();
+        match s.split_once('-') {
+            None => Version::parse(s).map(VersionRange::Single),
+            Some((lo, "")) => Version::parse(lo).map(VersionRange::OpenEnded),
+            Some((lo, hi)) => {
+                let lo = Version::parse(lo)?;
+                let hi = Version::parse(hi)?;
+                Some(VersionRange::Inclusive(lo, hi))
+            }
+        }
+    }
+
+    pub fn lower(&self) -> Version {
+        match *self {
+            VersionRange::Single(v) => v,
+            VersionRange::Inclusive(lo, _) => lo,
+            VersionRange::OpenEnded(lo) => lo,
+        }
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionRange::Single(v) => write!(f, "{v}"),
+            VersionRange::Inclusive(lo, hi) => write!(f, "{lo}-{hi}"),
+            VersionRange::OpenEnded(lo) => write!(f, "{lo}-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(major: u32, minor: u32) -> Version {
+        Version { major, minor }
+    }
+
+    #[test]
+    fn parses_single_version() {
+        assert_eq!(VersionRange::parse("1.95"), Some(VersionRange::Single(v(1, 95))));
+    }
+
+    #[test]
+    fn parses_inclusive_range() {
+        assert_eq!(
+            VersionRange::parse("1.86-1.93"),
+            Some(VersionRange::Inclusive(v(1, 86), v(1, 93)))
+        );
+    }
+
+    #[test]
+    fn parses_open_ended_range() {
+        assert_eq!(VersionRange::parse("1.89-"), Some(VersionRange::OpenEnded(v(1, 89))));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(VersionRange::parse("nightly"), None);
+        assert_eq!(VersionRange::parse(""), None);
+    }
+
+    #[test]
+    fn lower_matches_each_variant() {
+        assert_eq!(VersionRange::Single(v(1, 95)).lower(), v(1, 95));
+        assert_eq!(VersionRange::Inclusive(v(1, 86), v(1, 93)).lower(), v(1, 86));
+        assert_eq!(VersionRange::OpenEnded(v(1, 89)).lower(), v(1, 89));
+    }
+
+    #[test]
+    fn displays_round_trip_through_parse() {
+        for s in ["1.95", "1.86-1.93", "1.89-"] {
+            assert_eq!(VersionRange::parse(s).unwrap().to_string(), s);
+        }
+    }
+}
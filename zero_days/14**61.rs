@@ -1,4 +1,8 @@
 // affected versions: 1.48-1.92
+// category: unsound
+// expected error: E0038
+// expected phase: typeck-collect
+// current behavior: ICE
 trait Trait {
     type Assoc;
 }